@@ -0,0 +1,74 @@
+//! [actix-web](https://docs.rs/actix-web) response integration, gated
+//! behind the `actix` feature. Relies on [Error::http_status], so the
+//! `http` feature must also be enabled.
+
+use crate::Error;
+use actix_web::{http::StatusCode, HttpResponse, ResponseError};
+
+impl ResponseError for Error {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::from_u16(self.http_status()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        let mut builder = HttpResponse::build(self.status_code());
+
+        for (name, value) in self.headers() {
+            builder.insert_header((name, value));
+        }
+
+        if let Some(retry_after) = self.retry_after() {
+            builder.insert_header(("Retry-After", retry_after.as_secs().to_string()));
+        }
+
+        #[cfg(feature = "problem_details")]
+        let response = builder
+            .content_type("application/problem+json")
+            .json(self.to_problem_json());
+        #[cfg(not(feature = "problem_details"))]
+        let response = builder.body(self.to_string());
+
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CategoryExt;
+
+    #[test]
+    fn maps_the_category_s_http_status() {
+        let err = "foo".parse::<usize>().not_found().unwrap_err();
+
+        assert_eq!(err.status_code(), StatusCode::NOT_FOUND);
+    }
+
+    #[cfg(feature = "problem_details")]
+    #[test]
+    fn uses_the_problem_json_content_type() {
+        let err = "foo".parse::<usize>().not_found().unwrap_err();
+
+        let response = err.error_response();
+
+        assert_eq!(
+            response.headers().get("content-type").unwrap(),
+            "application/problem+json"
+        );
+    }
+
+    #[test]
+    fn carries_headers_and_retry_after() {
+        let err = "foo"
+            .parse::<usize>()
+            .too_many_requests()
+            .unwrap_err()
+            .with_header("X-Request-Id", "abc-123")
+            .with_retry_after(std::time::Duration::from_secs(30));
+
+        let response = err.error_response();
+
+        assert_eq!(response.headers().get("X-Request-Id").unwrap(), "abc-123");
+        assert_eq!(response.headers().get("Retry-After").unwrap(), "30");
+    }
+}
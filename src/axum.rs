@@ -0,0 +1,91 @@
+//! [axum](https://docs.rs/axum) response integration, gated behind the
+//! `axum` feature. Relies on [Error::http_status], so the `http` feature
+//! must also be enabled.
+
+use crate::Error;
+use ::axum::http::StatusCode;
+use ::axum::response::{IntoResponse, Response};
+
+impl IntoResponse for Error {
+    fn into_response(self) -> Response {
+        let status =
+            StatusCode::from_u16(self.http_status()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+
+        #[cfg(feature = "problem_details")]
+        let body = ::axum::Json(self.to_problem_json());
+        #[cfg(not(feature = "problem_details"))]
+        let body = self.to_string();
+
+        let mut response = (status, body).into_response();
+
+        #[cfg(feature = "problem_details")]
+        response.headers_mut().insert(
+            ::axum::http::header::CONTENT_TYPE,
+            ::axum::http::HeaderValue::from_static("application/problem+json"),
+        );
+
+        for (name, value) in self.headers() {
+            if let (Ok(name), Ok(value)) = (
+                ::axum::http::HeaderName::from_bytes(name.as_bytes()),
+                ::axum::http::HeaderValue::from_str(value),
+            ) {
+                response.headers_mut().insert(name, value);
+            }
+        }
+
+        if let Some(retry_after) = self.retry_after() {
+            if let Ok(value) =
+                ::axum::http::HeaderValue::from_str(&retry_after.as_secs().to_string())
+            {
+                response
+                    .headers_mut()
+                    .insert(::axum::http::header::RETRY_AFTER, value);
+            }
+        }
+
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CategoryExt;
+
+    #[test]
+    fn maps_the_category_s_http_status() {
+        let err = "foo".parse::<usize>().not_found().unwrap_err();
+
+        let response = err.into_response();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[cfg(feature = "problem_details")]
+    #[test]
+    fn uses_the_problem_json_content_type() {
+        let err = "foo".parse::<usize>().not_found().unwrap_err();
+
+        let response = err.into_response();
+
+        assert_eq!(
+            response.headers().get("content-type").unwrap(),
+            "application/problem+json"
+        );
+    }
+
+    #[test]
+    fn carries_headers_and_retry_after() {
+        let err = "foo"
+            .parse::<usize>()
+            .too_many_requests()
+            .unwrap_err()
+            .with_header("X-Request-Id", "abc-123")
+            .with_retry_after(std::time::Duration::from_secs(30));
+
+        let response = err.into_response();
+
+        assert_eq!(response.headers().get("X-Request-Id").unwrap(), "abc-123");
+        assert_eq!(response.headers().get("Retry-After").unwrap(), "30");
+    }
+}
@@ -1,49 +1,160 @@
 use crate::Error;
 
-/// What type of error this is. Roughly corresponds to HTTP error statuses.
-#[derive(Debug, Clone, PartialEq, Eq)]
-#[non_exhaustive]
-// TODO(shelbyd): Macro for cases.
-pub enum Category {
-    /// The client made an invalid request. Usually bad input.
-    BadRequest,
-
-    /// Fallback for custom error statuses. Will have fields based on if the `http`/`json_rpc` features are defined.
-    #[non_exhaustive]
-    Custom {
-        /// Status code for HTTP.
-        #[cfg(feature = "http")]
-        http_status: u16,
-
-        /// Status code for JSON-RPC.
-        #[cfg(feature = "json_rpc")]
-        json_rpc_status: i32,
-    },
+/// Defines [Category]'s variants along with the `CategoryExt` convenience
+/// method and the HTTP/JSON-RPC status codes for each, so every case only
+/// has to be written once.
+macro_rules! define_categories {
+    (
+        $(
+            $(#[$meta:meta])*
+            $variant:ident { method: $method:ident, http: $http:expr, json_rpc: $json_rpc:expr, title: $title:expr, client: $client:expr $(,)? }
+        ),* $(,)?
+    ) => {
+        /// What type of error this is. Roughly corresponds to HTTP error statuses.
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        #[non_exhaustive]
+        pub enum Category {
+            $(
+                $(#[$meta])*
+                $variant,
+            )*
+
+            /// Fallback for custom error statuses. Will have fields based on if the `http`/`json_rpc` features are defined.
+            ///
+            /// Deliberately left to just these two status fields: the
+            /// general-purpose side-channel for attaching extra response
+            /// data (headers, `Retry-After`, JSON-RPC `data`) lives on
+            /// [Error] itself — see the `metadata` module — rather than
+            /// being duplicated here.
+            #[non_exhaustive]
+            Custom {
+                /// Status code for HTTP.
+                #[cfg(feature = "http")]
+                http_status: u16,
+
+                /// Status code for JSON-RPC.
+                #[cfg(feature = "json_rpc")]
+                json_rpc_status: i32,
+            },
+        }
+
+        impl Category {
+            #[cfg(feature = "http")]
+            pub(crate) fn http_status_code(&self) -> u16 {
+                match self {
+                    $(Category::$variant => $http,)*
+                    Category::Custom { http_status, .. } => *http_status,
+                }
+            }
+
+            #[cfg(feature = "json_rpc")]
+            pub(crate) fn json_rpc_status_code(&self) -> i32 {
+                match self {
+                    $(Category::$variant => $json_rpc,)*
+                    Category::Custom { json_rpc_status, .. } => *json_rpc_status,
+                }
+            }
+
+            #[cfg(feature = "problem_details")]
+            pub(crate) fn problem_title(&self) -> &'static str {
+                match self {
+                    $(Category::$variant => $title,)*
+                    Category::Custom { .. } => "An error occurred",
+                }
+            }
+
+            /// True if this category represents a 4xx-class client error.
+            pub(crate) fn is_client_error(&self) -> bool {
+                match self {
+                    $(Category::$variant => $client,)*
+                    Category::Custom { .. } => custom_is_client_error(self),
+                }
+            }
+        }
+
+        /// Convenience trait for easily adding categories to errors.
+        pub trait CategoryExt {
+            /// The type that is returned from this trait's functions.
+            type Ret;
+
+            /// For internal use.
+            #[cfg_attr(feature = "backtrace", track_caller)]
+            fn _internal_error_mut(self, f: impl FnOnce(&mut Error)) -> Self::Ret;
+
+            /// Convenience trait for easily adding categories to errors.
+            #[cfg_attr(feature = "backtrace", track_caller)]
+            fn with_category(self, category: Category) -> Self::Ret
+            where
+                Self: Sized,
+            {
+                self._internal_error_mut(|e| e.category = Some(category))
+            }
+
+            $(
+                #[doc = concat!("Convenience method for [`Category::", stringify!($variant), "`].")]
+                #[cfg_attr(feature = "backtrace", track_caller)]
+                fn $method(self) -> Self::Ret
+                where
+                    Self: Sized,
+                {
+                    self.with_category(Category::$variant)
+                }
+            )*
+        }
+    };
 }
 
-/// Convenience trait for easily adding categories to errors.
-pub trait CategoryExt {
-    /// The type that is returned from this trait's functions.
-    type Ret;
+define_categories! {
+    /// The client made an invalid request. Usually bad input.
+    BadRequest { method: bad_request, http: 400, json_rpc: -32600, title: "Bad Request", client: true },
 
-    /// For internal use.
-    fn _internal_error_mut(self, f: impl FnOnce(&mut Error)) -> Self::Ret;
+    /// The client did not provide valid authentication credentials.
+    Unauthorized { method: unauthorized, http: 401, json_rpc: -32600, title: "Unauthorized", client: true },
 
-    /// Convenience trait for easily adding categories to errors.
-    fn with_category(self, category: Category) -> Self::Ret
-    where
-        Self: Sized,
-    {
-        self._internal_error_mut(|e| e.category = Some(category))
-    }
+    /// The client is authenticated but isn't allowed to perform this action.
+    Forbidden { method: forbidden, http: 403, json_rpc: -32600, title: "Forbidden", client: true },
 
-    /// Convenience method for [Category::BadRequest].
-    fn bad_request(self) -> Self::Ret
-    where
-        Self: Sized,
-    {
-        self.with_category(Category::BadRequest)
-    }
+    /// The requested resource doesn't exist.
+    NotFound { method: not_found, http: 404, json_rpc: -32600, title: "Not Found", client: true },
+
+    /// The request conflicts with the current state of the resource.
+    Conflict { method: conflict, http: 409, json_rpc: -32600, title: "Conflict", client: true },
+
+    /// The requested resource used to exist but has been permanently removed.
+    Gone { method: gone, http: 410, json_rpc: -32600, title: "Gone", client: true },
+
+    /// The request was well-formed but semantically invalid.
+    UnprocessableEntity { method: unprocessable_entity, http: 422, json_rpc: -32602, title: "Unprocessable Entity", client: true },
+
+    /// The client has sent too many requests in a given amount of time.
+    TooManyRequests { method: too_many_requests, http: 429, json_rpc: -32600, title: "Too Many Requests", client: true },
+
+    /// The server doesn't support the functionality required to fulfill the request.
+    NotImplemented { method: not_implemented, http: 501, json_rpc: -32001, title: "Not Implemented", client: false },
+
+    /// The server is temporarily unable to handle the request.
+    ServiceUnavailable { method: service_unavailable, http: 503, json_rpc: -32002, title: "Service Unavailable", client: false },
+}
+
+/// Classifies a [Category::Custom] as client- or server-side using whichever
+/// status code feature is enabled, preferring `http`. Without either feature
+/// there's no status information to go on, so it's treated as a server error.
+#[cfg(feature = "http")]
+fn custom_is_client_error(category: &Category) -> bool {
+    matches!(category, Category::Custom { http_status, .. } if (400..500).contains(http_status))
+}
+
+#[cfg(all(not(feature = "http"), feature = "json_rpc"))]
+fn custom_is_client_error(category: &Category) -> bool {
+    matches!(
+        category,
+        Category::Custom { json_rpc_status, .. } if matches!(json_rpc_status, -32600 | -32602)
+    )
+}
+
+#[cfg(all(not(feature = "http"), not(feature = "json_rpc")))]
+fn custom_is_client_error(_category: &Category) -> bool {
+    false
 }
 
 impl<T, E> CategoryExt for Result<T, E>
@@ -52,11 +163,23 @@ where
 {
     type Ret = Result<T, Error>;
 
+    #[cfg_attr(feature = "backtrace", track_caller)]
     fn _internal_error_mut(self, f: impl FnOnce(&mut Error)) -> Self::Ret {
         match self {
             Ok(t) => Ok(t),
             Err(e) => {
                 let mut e = e.into();
+
+                // `e.into()` may have captured a location inside this crate
+                // (e.g. through `Into`'s non-tracked blanket impl), so
+                // recapture here where `#[track_caller]` reflects the
+                // caller's `.bad_request()`/`.with_category()` call site.
+                #[cfg(feature = "backtrace")]
+                {
+                    e.location = std::panic::Location::caller();
+                    e.backtrace = std::backtrace::Backtrace::capture();
+                }
+
                 f(&mut e);
                 Err(e)
             }
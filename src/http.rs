@@ -5,9 +5,5 @@ pub(crate) fn status_code(category: Option<&Category>) -> u16 {
         return 500;
     };
 
-    match category {
-        Category::BadRequest => 400,
-
-        Category::Custom { http_status, .. } => *http_status,
-    }
+    category.http_status_code()
 }
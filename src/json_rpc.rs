@@ -1,15 +1,94 @@
-use crate::Category;
+use crate::{Category, Error};
 
 pub(crate) fn status_code(category: Option<&Category>) -> i32 {
     let Some(category) = category else {
         return -32000;
     };
 
-    match category {
-        Category::BadRequest => -32600,
+    category.json_rpc_status_code()
+}
+
+/// Builds the JSON-RPC error object's optional `data` member from the
+/// error's attached [Error::with_rpc_data] payload, plus its location and
+/// backtrace when the `backtrace` feature is enabled.
+///
+/// Location/backtrace are only merged in when `rpc_data` is itself an
+/// object (so they can be added as siblings of the user's own keys); a
+/// non-object payload (e.g. an array or string) is returned untouched,
+/// so that enabling the orthogonal `backtrace` feature never changes the
+/// shape of a payload [Error::with_rpc_data] was given.
+#[cfg(feature = "backtrace")]
+pub(crate) fn data(error: &Error) -> Option<serde_json::Value> {
+    use serde_json::{Map, Value};
+
+    match error.rpc_data() {
+        Some(Value::Object(map)) => {
+            let mut map = map.clone();
+            if let Some(location) = error.location() {
+                map.insert("location".to_string(), Value::String(location.to_string()));
+            }
+            if let Some(backtrace) = error.backtrace() {
+                map.insert("backtrace".to_string(), Value::String(backtrace.to_string()));
+            }
+            Some(Value::Object(map))
+        }
+        Some(other) => Some(other.clone()),
+        None => {
+            let mut map = Map::new();
+            if let Some(location) = error.location() {
+                map.insert("location".to_string(), Value::String(location.to_string()));
+            }
+            if let Some(backtrace) = error.backtrace() {
+                map.insert("backtrace".to_string(), Value::String(backtrace.to_string()));
+            }
+            if map.is_empty() {
+                None
+            } else {
+                Some(Value::Object(map))
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "backtrace"))]
+pub(crate) fn data(error: &Error) -> Option<serde_json::Value> {
+    error.rpc_data().cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::CategoryExt;
+
+    #[test]
+    fn no_rpc_data_and_no_backtrace_is_none() {
+        let err = "foo".parse::<usize>().bad_request().unwrap_err();
+
+        #[cfg(not(feature = "backtrace"))]
+        assert_eq!(err.json_rpc_data(), None);
+        #[cfg(feature = "backtrace")]
+        assert!(err.json_rpc_data().is_some());
+    }
+
+    #[test]
+    fn object_rpc_data_round_trips() {
+        let err = "foo"
+            .parse::<usize>()
+            .bad_request()
+            .unwrap_err()
+            .with_rpc_data(serde_json::json!({ "field": "id" }));
+
+        let data = err.json_rpc_data().unwrap();
+        assert_eq!(data["field"], "id");
+    }
+
+    #[test]
+    fn non_object_rpc_data_keeps_its_shape_regardless_of_the_backtrace_feature() {
+        let err = "foo"
+            .parse::<usize>()
+            .bad_request()
+            .unwrap_err()
+            .with_rpc_data(serde_json::json!(["a", "b"]));
 
-        Category::Custom {
-            json_rpc_status, ..
-        } => *json_rpc_status,
+        assert_eq!(err.json_rpc_data(), Some(serde_json::json!(["a", "b"])));
     }
 }
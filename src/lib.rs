@@ -22,6 +22,17 @@ mod http;
 #[cfg(feature = "json_rpc")]
 mod json_rpc;
 
+#[cfg(feature = "problem_details")]
+mod problem;
+
+#[cfg(feature = "axum")]
+mod axum;
+
+#[cfg(feature = "actix")]
+mod actix;
+
+mod metadata;
+
 use std::fmt::Display;
 
 pub use category::{Category, CategoryExt};
@@ -35,6 +46,17 @@ pub type Result<T> = std::result::Result<T, Error>;
 pub struct Error {
     anyhow: anyhow::Error,
     category: Option<Category>,
+
+    #[cfg(feature = "problem_details")]
+    problem_extensions: Vec<(String, serde_json::Value)>,
+
+    #[cfg(feature = "backtrace")]
+    location: &'static std::panic::Location<'static>,
+
+    #[cfg(feature = "backtrace")]
+    backtrace: std::backtrace::Backtrace,
+
+    metadata: Option<Box<metadata::Metadata>>,
 }
 
 impl Error {
@@ -68,16 +90,127 @@ impl Error {
     pub fn json_rpc_status(&self) -> i32 {
         json_rpc::status_code(self.category.as_ref())
     }
+
+    #[cfg(feature = "json_rpc")]
+    /// The JSON-RPC error object's optional `data` member for this error,
+    /// assembled from [Error::with_rpc_data] plus, when the `backtrace`
+    /// feature is enabled, its location and backtrace.
+    pub fn json_rpc_data(&self) -> Option<serde_json::Value> {
+        json_rpc::data(self)
+    }
+
+    /// True if this error's category represents a 4xx-class client error.
+    pub fn is_client_error(&self) -> bool {
+        self.category
+            .as_ref()
+            .map(Category::is_client_error)
+            .unwrap_or(false)
+    }
+
+    /// True if this error's category represents a 5xx-class server error,
+    /// or if it has no category at all.
+    pub fn is_server_error(&self) -> bool {
+        !self.is_client_error()
+    }
+
+    /// True if this error has a category and `predicate` returns true for it.
+    pub fn is_category(&self, predicate: impl Fn(&Category) -> bool) -> bool {
+        self.category.as_ref().map(predicate).unwrap_or(false)
+    }
+
+    /// Attempt to downcast this error's underlying cause to a concrete type.
+    /// See [anyhow::Error::downcast_ref].
+    pub fn downcast_ref<E>(&self) -> Option<&E>
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        self.anyhow.downcast_ref::<E>()
+    }
+
+    /// Attempt to downcast this error's underlying cause to a concrete type,
+    /// returning `self` unchanged if it doesn't match. See
+    /// [anyhow::Error::downcast].
+    pub fn downcast<E>(self) -> std::result::Result<E, Self>
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        let Error {
+            anyhow,
+            category,
+            #[cfg(feature = "problem_details")]
+            problem_extensions,
+            #[cfg(feature = "backtrace")]
+            location,
+            #[cfg(feature = "backtrace")]
+            backtrace,
+            metadata,
+        } = self;
+
+        anyhow.downcast::<E>().map_err(|anyhow| Error {
+            anyhow,
+            category,
+
+            #[cfg(feature = "problem_details")]
+            problem_extensions,
+
+            #[cfg(feature = "backtrace")]
+            location,
+
+            #[cfg(feature = "backtrace")]
+            backtrace,
+
+            metadata,
+        })
+    }
+
+    #[cfg(feature = "backtrace")]
+    /// The source location of the call that produced this error, captured
+    /// at the `.bad_request()?`/`?` call site rather than inside this crate.
+    pub fn location(&self) -> Option<&std::panic::Location<'static>> {
+        Some(self.location)
+    }
+
+    #[cfg(feature = "backtrace")]
+    /// The backtrace captured when this error was produced. Empty unless
+    /// backtraces are enabled, e.g. via `RUST_BACKTRACE=1`. See
+    /// [std::backtrace::Backtrace::capture].
+    pub fn backtrace(&self) -> Option<&std::backtrace::Backtrace> {
+        Some(&self.backtrace)
+    }
+}
+
+impl std::fmt::Debug for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(&self.anyhow, f)
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.anyhow, f)
+    }
 }
 
 impl<E> From<E> for Error
 where
     anyhow::Error: From<E>,
 {
+    #[cfg_attr(feature = "backtrace", track_caller)]
     fn from(e: E) -> Self {
         Error {
             anyhow: anyhow::Error::from(e),
             category: None,
+
+            #[cfg(feature = "problem_details")]
+            problem_extensions: Vec::new(),
+
+            #[cfg(feature = "backtrace")]
+            location: std::panic::Location::caller(),
+
+            #[cfg(feature = "backtrace")]
+            backtrace: std::backtrace::Backtrace::capture(),
+
+            metadata: None,
         }
     }
 }
@@ -168,4 +301,52 @@ mod tests {
     fn can_attach_context() {
         let _ = "foo".parse::<usize>().bad_request().context("Some context");
     }
+
+    #[test]
+    fn bad_request_is_a_client_error() {
+        let err = "foo".parse::<usize>().bad_request().unwrap_err();
+
+        assert!(err.is_client_error());
+        assert!(!err.is_server_error());
+    }
+
+    #[test]
+    fn uncategorized_is_a_server_error() {
+        let err: Error = anyhow::anyhow!("boom").into();
+
+        assert!(!err.is_client_error());
+        assert!(err.is_server_error());
+    }
+
+    #[test]
+    fn downcasts_to_the_original_cause() {
+        let err = "foo".parse::<usize>().bad_request().unwrap_err();
+
+        let parse_err = err.downcast::<std::num::ParseIntError>();
+        assert!(parse_err.is_ok());
+    }
+
+    #[test]
+    fn failed_downcast_preserves_the_category() {
+        let err = "foo".parse::<usize>().bad_request().unwrap_err();
+
+        let err = err.downcast::<std::io::Error>().unwrap_err();
+        assert_eq!(err.category(), Some(&Category::BadRequest));
+    }
+
+    #[cfg(feature = "backtrace")]
+    #[test]
+    fn captures_the_caller_s_location() {
+        let err = "foo".parse::<usize>().bad_request().unwrap_err();
+
+        assert_eq!(err.location().unwrap().file(), file!());
+    }
+
+    #[cfg(feature = "backtrace")]
+    #[test]
+    fn captures_a_backtrace() {
+        let err = "foo".parse::<usize>().bad_request().unwrap_err();
+
+        assert!(err.backtrace().is_some());
+    }
 }
@@ -0,0 +1,110 @@
+//! Side-channel for response-shaping metadata (headers, JSON-RPC `data`)
+//! that the `axum`/`actix`/`json_rpc` integrations read when building a
+//! response. The `http` feature on its own only exposes the attached
+//! headers through [Error::headers] and [Error::retry_after]; it does not
+//! build a response.
+
+use crate::Error;
+#[cfg(feature = "http")]
+use std::time::Duration;
+
+#[derive(Default)]
+pub(crate) struct Metadata {
+    #[cfg(feature = "http")]
+    headers: Vec<(String, String)>,
+
+    #[cfg(feature = "http")]
+    retry_after: Option<Duration>,
+
+    #[cfg(feature = "json_rpc")]
+    rpc_data: Option<serde_json::Value>,
+}
+
+impl Error {
+    #[cfg(feature = "http")]
+    /// Attach an HTTP response header, set by the `axum`/`actix` response
+    /// integrations.
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata_mut().headers.push((name.into(), value.into()));
+        self
+    }
+
+    #[cfg(feature = "http")]
+    /// Attach a `Retry-After` duration, set as that header by the
+    /// `axum`/`actix` response integrations.
+    pub fn with_retry_after(mut self, duration: Duration) -> Self {
+        self.metadata_mut().retry_after = Some(duration);
+        self
+    }
+
+    #[cfg(feature = "json_rpc")]
+    /// Attach a free-form payload for the JSON-RPC error object's optional
+    /// `data` member.
+    pub fn with_rpc_data(mut self, data: serde_json::Value) -> Self {
+        self.metadata_mut().rpc_data = Some(data);
+        self
+    }
+
+    #[cfg(feature = "http")]
+    /// Headers attached via [Error::with_header].
+    pub fn headers(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.metadata
+            .iter()
+            .flat_map(|m| m.headers.iter())
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    #[cfg(feature = "http")]
+    /// The `Retry-After` duration attached via [Error::with_retry_after].
+    pub fn retry_after(&self) -> Option<Duration> {
+        self.metadata.as_ref().and_then(|m| m.retry_after)
+    }
+
+    #[cfg(feature = "json_rpc")]
+    /// The JSON-RPC `data` payload attached via [Error::with_rpc_data].
+    pub fn rpc_data(&self) -> Option<&serde_json::Value> {
+        self.metadata.as_ref().and_then(|m| m.rpc_data.as_ref())
+    }
+
+    #[cfg(any(feature = "http", feature = "json_rpc"))]
+    pub(crate) fn metadata_mut(&mut self) -> &mut Metadata {
+        self.metadata.get_or_insert_with(|| Box::new(Metadata::default()))
+    }
+}
+
+#[cfg(all(test, any(feature = "http", feature = "json_rpc")))]
+mod tests {
+    use crate::CategoryExt;
+
+    #[cfg(feature = "http")]
+    use std::time::Duration;
+
+    #[cfg(feature = "http")]
+    #[test]
+    fn attaches_headers_and_retry_after() {
+        let err = "foo"
+            .parse::<usize>()
+            .too_many_requests()
+            .unwrap_err()
+            .with_header("X-Request-Id", "abc-123")
+            .with_retry_after(Duration::from_secs(30));
+
+        assert_eq!(
+            err.headers().collect::<Vec<_>>(),
+            vec![("X-Request-Id", "abc-123")]
+        );
+        assert_eq!(err.retry_after(), Some(Duration::from_secs(30)));
+    }
+
+    #[cfg(feature = "json_rpc")]
+    #[test]
+    fn attaches_rpc_data() {
+        let err = "foo"
+            .parse::<usize>()
+            .bad_request()
+            .unwrap_err()
+            .with_rpc_data(serde_json::json!({ "field": "id" }));
+
+        assert_eq!(err.rpc_data(), Some(&serde_json::json!({ "field": "id" })));
+    }
+}
@@ -0,0 +1,126 @@
+//! [RFC 7807](https://www.rfc-editor.org/rfc/rfc7807) `application/problem+json`
+//! rendering, gated behind the `problem_details` feature. Relies on
+//! [Error::http_status], so the `http` feature must also be enabled.
+
+use crate::Error;
+use serde_json::{Map, Value};
+
+/// Keys reserved for the core RFC 7807 members, plus `location` and
+/// `backtrace` when the `backtrace` feature adds those. Extension members
+/// using one of these names are dropped when rendering so they can't shadow
+/// a built-in member.
+#[cfg(not(feature = "backtrace"))]
+const RESERVED_KEYS: &[&str] = &["type", "title", "status", "detail"];
+
+#[cfg(feature = "backtrace")]
+const RESERVED_KEYS: &[&str] = &["type", "title", "status", "detail", "location", "backtrace"];
+
+impl Error {
+    /// Attach an extension member that will be serialized as a top-level
+    /// sibling of `type`/`title`/`status`/`detail` by [Error::to_problem_json].
+    ///
+    /// Keys matching a reserved RFC 7807 member (`type`, `title`, `status`,
+    /// `detail`) are ignored, as are `location` and `backtrace` when the
+    /// `backtrace` feature is enabled.
+    pub fn with_problem_extension<T>(mut self, key: impl Into<String>, value: T) -> Self
+    where
+        T: serde::Serialize,
+    {
+        let key = key.into();
+
+        if let Ok(value) = serde_json::to_value(value) {
+            self.problem_extensions.push((key, value));
+        }
+
+        self
+    }
+
+    /// Render this error as an [RFC 7807](https://www.rfc-editor.org/rfc/rfc7807)
+    /// `application/problem+json` document.
+    pub fn to_problem_json(&self) -> Value {
+        let mut map = Map::new();
+
+        map.insert("type".to_string(), Value::String("about:blank".to_string()));
+        map.insert(
+            "title".to_string(),
+            Value::String(
+                self.category
+                    .as_ref()
+                    .map(|c| c.problem_title())
+                    .unwrap_or("Internal Server Error")
+                    .to_string(),
+            ),
+        );
+        map.insert("status".to_string(), Value::from(self.http_status()));
+        map.insert(
+            "detail".to_string(),
+            Value::String(format!("{:#}", self.anyhow)),
+        );
+
+        #[cfg(feature = "backtrace")]
+        {
+            map.insert(
+                "location".to_string(),
+                Value::String(self.location().unwrap().to_string()),
+            );
+            map.insert(
+                "backtrace".to_string(),
+                Value::String(self.backtrace().unwrap().to_string()),
+            );
+        }
+
+        for (key, value) in &self.problem_extensions {
+            if RESERVED_KEYS.contains(&key.as_str()) {
+                continue;
+            }
+            map.insert(key.clone(), value.clone());
+        }
+
+        Value::Object(map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CategoryExt;
+
+    #[test]
+    fn uncategorized_error_is_still_a_valid_problem() {
+        let err = anyhow::anyhow!("boom");
+        let problem = Error::from(err).to_problem_json();
+
+        assert_eq!(problem["status"], 500);
+        assert_eq!(problem["title"], "Internal Server Error");
+    }
+
+    #[test]
+    fn categorized_error_uses_its_title_and_status() {
+        let problem = "foo"
+            .parse::<usize>()
+            .not_found()
+            .unwrap_err()
+            .to_problem_json();
+
+        assert_eq!(problem["status"], 404);
+        assert_eq!(problem["title"], "Not Found");
+    }
+
+    #[test]
+    fn extensions_serialize_as_siblings() {
+        let problem = Error::from(anyhow::Error::msg("boom"))
+            .with_problem_extension("trace_id", "abc-123")
+            .to_problem_json();
+
+        assert_eq!(problem["trace_id"], "abc-123");
+    }
+
+    #[test]
+    fn extensions_cannot_shadow_reserved_keys() {
+        let problem = Error::from(anyhow::Error::msg("boom"))
+            .with_problem_extension("status", 999)
+            .to_problem_json();
+
+        assert_eq!(problem["status"], 500);
+    }
+}